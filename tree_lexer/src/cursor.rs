@@ -63,6 +63,11 @@ impl<'a> Cursor<'a> {
         self.chars.as_str().is_empty()
     }
 
+    /// Returns the remaining, not yet consumed, input as a `&str`
+    pub(crate) fn as_str(&self) -> &'a str {
+        self.chars.as_str()
+    }
+
     /// Peeks at the current character,
     /// returns an EOF char if the position cannot be found
     pub(crate) fn peek_first(&self) -> char {
@@ -89,7 +94,7 @@ impl<'a> Cursor<'a> {
                     self.increment_column();
                 }
 
-                return Some(c);
+                Some(c)
             }
             None => None,
         }
@@ -5,6 +5,7 @@ use unicode_xid::UnicodeXID;
 use crate::cursor::{Cursor, EOF};
 
 mod cursor;
+pub mod unescape;
 
 /// Represents the kind of token
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -72,6 +73,18 @@ pub enum TokenKind {
     Or,
     /// Whitespace
     Whitespace,
+    /// `// ...`, up to (but not including) the newline
+    LineComment,
+    /// `/* ... */`, including nested block comments
+    BlockComment {
+        /// `false` if EOF was hit before the matching `*/`.
+        terminated: bool,
+    },
+    /// An unrecognized character that doesn't start any other token.
+    ///
+    /// Kept around (rather than panicking) so callers can recover and
+    /// report a diagnostic for it.
+    Unknown,
 }
 
 /// Represents the type of literal,
@@ -83,8 +96,34 @@ pub enum LiteralKind {
     Ident {
         keyword: bool,
     },
-    Number(Base),
-    String,
+    Int {
+        base: Base,
+        /// `true` if the literal had no digits after the base prefix,
+        /// e.g. `0x` with nothing following it.
+        empty_int: bool,
+    },
+    Float {
+        base: Base,
+        /// `true` if an `e`/`E` was seen with no digits (and optional sign)
+        /// following it, e.g. `1e`.
+        empty_exponent: bool,
+        /// `true` if `base` isn't `Decimal`, e.g. `0x1.5` or `0b1e2` — a
+        /// fractional or exponent part on a non-decimal literal, which is invalid.
+        invalid_base_for_float: bool,
+    },
+    String {
+        /// `false` if the closing `"` was never found, e.g. EOF was hit first.
+        terminated: bool,
+    },
+    RawString {
+        /// The number of `#`s the literal was opened with, e.g. `2` for `r##"..`.
+        n_hashes: usize,
+        /// `false` if the opening `"` was missing, e.g. a bare `r#` followed
+        /// by something other than a quote.
+        started: bool,
+        /// `false` if EOF was hit before a closing `"` followed by `n_hashes` `#`s.
+        terminated: bool,
+    },
     Bool,
 }
 
@@ -92,6 +131,7 @@ pub enum LiteralKind {
 #[derive(Debug, PartialEq, PartialOrd)]
 pub enum Base {
     Hexadecimal,
+    Octal,
     Decimal,
     Binary,
 }
@@ -111,15 +151,26 @@ pub struct Token {
     pub location: Location,
     /// The length of the token
     pub length: usize,
+    /// Byte offset into `literal` where a trailing suffix (e.g. the `u8` in
+    /// `123u8`, or the `s` in `"text"s`) begins. Equal to `length` when the
+    /// literal has no suffix.
+    pub suffix_start: usize,
 }
 
 impl Token {
-    fn new(kind: TokenKind, literal: String, location: Location, length: usize) -> Token {
+    fn new(
+        kind: TokenKind,
+        literal: String,
+        location: Location,
+        length: usize,
+        suffix_start: usize,
+    ) -> Token {
         Token {
             kind,
             literal,
             location,
             length,
+            suffix_start,
         }
     }
 }
@@ -194,73 +245,169 @@ impl Cursor<'_> {
         ret
     }
 
-    /// Gobbles up the number literal (can only be base 2, 10 and 16) and returns it as a `String`.
-    ///
-    /// Panics
+    /// Gobbles up case-insensitive octal digits and returns them as a `String`.
+    /// Ignores `_` characters to allow underscores in the number literal for readability.
+    fn eat_octal_digits(&mut self) -> String {
+        let mut ret = String::new();
+
+        loop {
+            match self.peek_first() {
+                '_' => {
+                    self.advance();
+                }
+                c @ '0'..='7' => {
+                    ret.push(c);
+                    self.advance();
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Gobbles up the number literal (can only be base 2, 8, 10 and 16) and returns it
+    /// as a `String`, along with its `Base` and whether it was left `empty`
+    /// (a base prefix with no digits after it, e.g. `0x`).
     ///
-    /// - If the number literal attempts to specify a base that is invalid,
-    /// e.g. `0p123`
-    fn eat_number(&mut self) -> (String, Base) {
+    /// Never panics: a `0` followed by an unrecognized base letter (e.g. `0p123`)
+    /// is treated as a lone decimal `0`, leaving the rest for the next token.
+    fn eat_number(&mut self) -> (String, Base, bool) {
         if self.peek_first() == '0' {
             match self.peek_second() {
                 'x' | 'X' => {
                     self.advance();
                     self.advance();
-                    (self.eat_hexadecimal_digits(), Base::Hexadecimal)
+                    let digits = self.eat_hexadecimal_digits();
+                    let empty = digits.is_empty();
+                    (digits, Base::Hexadecimal, empty)
+                }
+                'o' | 'O' => {
+                    self.advance();
+                    self.advance();
+                    let digits = self.eat_octal_digits();
+                    let empty = digits.is_empty();
+                    (digits, Base::Octal, empty)
                 }
                 'b' | 'B' => {
                     self.advance();
                     self.advance();
-                    (self.eat_binary_digits(), Base::Binary)
+                    let digits = self.eat_binary_digits();
+                    let empty = digits.is_empty();
+                    (digits, Base::Binary, empty)
                 }
                 // allow underscores as we allow underscores in the number literal
                 // as we allow them in eat_x_digits methods for readability
-                '0'..='9' | '_' => (self.eat_decimal_digits(), Base::Decimal),
-                // TODO: actual error message
-                _ => panic!("Unexpected character after 0"),
+                '0'..='9' | '_' => (self.eat_decimal_digits(), Base::Decimal, false),
+                _ => {
+                    self.advance(); // eat the lone `0`
+                    (String::from("0"), Base::Decimal, false)
+                }
             }
         } else {
-            (self.eat_decimal_digits(), Base::Decimal)
+            (self.eat_decimal_digits(), Base::Decimal, false)
         }
     }
 
-    /// Gobbles up a comment
+    /// Gobbles up an optional exponent sign followed by a run of decimal digits,
+    /// returning `true` if at least one digit was consumed.
+    fn eat_exponent_digits(&mut self) -> bool {
+        if matches!(self.peek_first(), '+' | '-') {
+            self.advance();
+        }
+
+        !self.eat_decimal_digits().is_empty()
+    }
+
+    /// Gobbles up a full numeric literal, classifying it as `LiteralKind::Int`
+    /// if it is a bare integer, or `LiteralKind::Float` if it has a fractional
+    /// part and/or an exponent.
+    ///
+    /// Never panics: a missing base prefix/exponent digits is recorded via
+    /// `empty_int`/`empty_exponent` instead. A fractional or exponent part on
+    /// a non-decimal base (e.g. `0x1.5`) is still consumed, but flagged via
+    /// `invalid_base_for_float` on the resulting `Float` for the caller to reject.
+    fn eat_numeric_literal(&mut self) -> LiteralKind {
+        let (_, base, empty_int) = self.eat_number();
+
+        if empty_int {
+            return LiteralKind::Int { base, empty_int };
+        }
+
+        let invalid_base_for_float = !matches!(base, Base::Decimal);
+
+        match self.peek_first() {
+            '.' if self.peek_second().is_ascii_digit() => {
+                self.advance(); // eat '.'
+                self.eat_decimal_digits();
+
+                let empty_exponent = if matches!(self.peek_first(), 'e' | 'E') {
+                    self.advance();
+                    !self.eat_exponent_digits()
+                } else {
+                    false
+                };
+
+                LiteralKind::Float {
+                    base,
+                    empty_exponent,
+                    invalid_base_for_float,
+                }
+            }
+            'e' | 'E' => {
+                self.advance();
+                let empty_exponent = !self.eat_exponent_digits();
+                LiteralKind::Float {
+                    base,
+                    empty_exponent,
+                    invalid_base_for_float,
+                }
+            }
+            _ => LiteralKind::Int { base, empty_int: false },
+        }
+    }
+
+    /// Gobbles up a comment and returns it as a `TokenKind::LineComment`.
     ///
     /// Regular comments start with `two forward slashes // and end with a newline`
-    fn eat_comment(&mut self) {
+    fn eat_comment(&mut self) -> TokenKind {
         self.eat_while(|c| c != '\n');
+        TokenKind::LineComment
     }
 
-    /// Gobbles up a multiline comment
+    /// Gobbles up a multiline comment and returns it as a `TokenKind::BlockComment`.
+    ///
+    /// Multiline comments start with `/*`, and end with `*/`, and can be nested.
     ///
-    /// Multiline comments start with `/*`, and end with `*/`
-    fn eat_multiline_comment(&mut self) {
+    /// Never panics: if EOF is hit before the depth returns to `0`, the
+    /// returned `BlockComment` has `terminated: false`.
+    fn eat_multiline_comment(&mut self) -> TokenKind {
         let mut depth = 0;
         while let Some(c) = self.advance() {
             if c == '*' && self.peek_first() == '/' {
                 depth -= 1;
                 self.advance();
                 if depth == 0 {
-                    break;
+                    return TokenKind::BlockComment { terminated: true };
                 }
             } else if c == '/' && self.peek_first() == '*' {
                 depth += 1;
                 self.advance();
             }
         }
+
+        TokenKind::BlockComment { terminated: false }
     }
 
     /// Gobbles up an identifier, and returns it as a `String`,
     /// Identifier naming rules follow Rusts naming rules.
     ///
-    /// Panics
-    ///
-    /// - If the identifier has a bad start
+    /// Assumes the current character is already a valid identifier start
+    /// (`is_xid_start`); callers are expected to check this before dispatching
+    /// here rather than have this method panic on a bad start.
     fn eat_ident(&mut self) -> String {
-        if !is_xid_start(self.peek_first()){
-            panic!("bad identifier start"); // TODO: actual error message
-        }
-
         // move past ident start
         let mut ret = String::from(self.advance().unwrap());
         ret.push_str(self.eat_while(is_xid_continue).as_str());
@@ -268,7 +415,15 @@ impl Cursor<'_> {
         ret
     }
 
-    fn eat_double_quoted_string(&mut self) -> String {
+    /// Gobbles up a double quoted string and returns its raw contents (escape
+    /// sequences left untouched) as a `String`, along with whether the
+    /// closing quote was found (`terminated`).
+    ///
+    /// This does not interpret escape sequences at all, it just makes sure a
+    /// `\"` doesn't end the string early; decoding and validating the
+    /// escapes is left to [`crate::unescape`], so errors there carry precise
+    /// spans instead of unwinding the cursor.
+    fn eat_double_quoted_string(&mut self) -> (String, bool) {
         let mut ret = String::new();
 
         self.advance(); // eat start quote
@@ -276,54 +431,295 @@ impl Cursor<'_> {
         loop {
             match self.peek_first() {
                 '\\' => {
-                    self.advance(); // eat escape backslash
+                    ret.push(self.advance().unwrap()); // keep the backslash as-is
                     match self.peek_first() {
-                        '\\' => {
-                            ret.push('\\');
-                            self.advance();
-                        },
-                        'n' => {
-                            ret.push('\n');
-                            self.advance();
-                        },
-                        'r' => {
-                            ret.push('\r');
-                            self.advance();
-                        },
-                        't' => {
-                            ret.push('\t');
-                            self.advance();
-                        },
-                        '"' => {
-                            ret.push('"');
+                        EOF => return (ret, false),
+                        c => {
+                            ret.push(c);
                             self.advance();
-                        },
-                        '\'' => {
-                            ret.push('\'');
-                            self.advance();
-                        },
-                        _ => {
-                            panic!("unexpected escape sequence"); // TODO: actual error message
                         }
                     }
                 },
                 '"' => {
                     self.advance();
-                    break;
+                    return (ret, true);
                 },
                 EOF => {
-                    panic!("unterminated string"); // TODO: actual error message
+                    return (ret, false);
                 },
                 _ => {
                     ret.push(self.advance().unwrap());
                 }
             }
         }
+    }
 
-        ret
+    /// Gobbles up a raw string literal (`r"..."`, `r#"..."#`, `r##"..."##`, ...),
+    /// having already consumed the leading `r`. Returns the number of `#`s the
+    /// literal was opened with, whether the opening `"` was found (`started`),
+    /// and whether the matching closing delimiter was found (`terminated`).
+    ///
+    /// Never panics: a missing opening quote or EOF before the closing
+    /// delimiter is recorded via `started`/`terminated` instead.
+    fn eat_raw_string(&mut self) -> (usize, bool, bool) {
+        let mut n_hashes = 0;
+        while self.peek_first() == '#' {
+            self.advance();
+            n_hashes += 1;
+        }
+
+        if self.peek_first() != '"' {
+            return (n_hashes, false, false);
+        }
+        self.advance(); // eat opening quote
+
+        loop {
+            match self.peek_first() {
+                EOF => return (n_hashes, true, false),
+                '"' => {
+                    self.advance();
+
+                    let mut closing_hashes = 0;
+                    while closing_hashes < n_hashes && self.peek_first() == '#' {
+                        self.advance();
+                        closing_hashes += 1;
+                    }
+
+                    if closing_hashes == n_hashes {
+                        return (n_hashes, true, true);
+                    }
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    /// Lexes and returns a single `Token`, starting at the cursor's current position.
+    ///
+    /// Should only be called when the cursor is not at EOF.
+    fn advance_token(&mut self) -> Token {
+        self.reset_consumed();
+        let location = self.location();
+        let remaining = self.as_str();
+
+        let kind = match self.peek_first() {
+            c if c.is_whitespace() => {
+                self.eat_while(char::is_whitespace);
+                TokenKind::Whitespace
+            }
+            '/' if self.peek_second() == '/' => self.eat_comment(),
+            '/' if self.peek_second() == '*' => self.eat_multiline_comment(),
+            '0'..='9' => TokenKind::Literal(self.eat_numeric_literal()),
+            '"' => {
+                let (_, terminated) = self.eat_double_quoted_string();
+                TokenKind::Literal(LiteralKind::String { terminated })
+            }
+            'r' if matches!(self.peek_second(), '#' | '"') => {
+                self.advance(); // eat 'r'
+                let (n_hashes, started, terminated) = self.eat_raw_string();
+                TokenKind::Literal(LiteralKind::RawString {
+                    n_hashes,
+                    started,
+                    terminated,
+                })
+            }
+            c if is_xid_start(c) => {
+                self.eat_ident();
+                TokenKind::Literal(LiteralKind::Ident { keyword: false })
+            }
+            '+' => {
+                self.advance();
+                TokenKind::Add
+            }
+            '-' => {
+                self.advance();
+                TokenKind::Minus
+            }
+            '/' => {
+                self.advance();
+                TokenKind::Divide
+            }
+            '*' => {
+                self.advance();
+                TokenKind::Multiply
+            }
+            '%' => {
+                self.advance();
+                TokenKind::Modulo
+            }
+            '.' => {
+                self.advance();
+                TokenKind::Dot
+            }
+            ',' => {
+                self.advance();
+                TokenKind::Comma
+            }
+            ';' => {
+                self.advance();
+                TokenKind::Semicolon
+            }
+            ':' => {
+                self.advance();
+                TokenKind::Colon
+            }
+            '(' => {
+                self.advance();
+                TokenKind::LeftParen
+            }
+            ')' => {
+                self.advance();
+                TokenKind::RightParen
+            }
+            '{' => {
+                self.advance();
+                TokenKind::LeftBrace
+            }
+            '}' => {
+                self.advance();
+                TokenKind::RightBrace
+            }
+            '[' => {
+                self.advance();
+                TokenKind::LeftBracket
+            }
+            ']' => {
+                self.advance();
+                TokenKind::RightBracket
+            }
+            '<' => {
+                self.advance();
+                if self.peek_first() == '=' {
+                    self.advance();
+                    TokenKind::LessThanEqual
+                } else {
+                    TokenKind::LessThan
+                }
+            }
+            '>' => {
+                self.advance();
+                if self.peek_first() == '=' {
+                    self.advance();
+                    TokenKind::GreaterThanEqual
+                } else {
+                    TokenKind::GreaterThan
+                }
+            }
+            '=' => {
+                self.advance();
+                if self.peek_first() == '=' {
+                    self.advance();
+                    TokenKind::Equal
+                } else {
+                    TokenKind::Assign
+                }
+            }
+            '!' => {
+                self.advance();
+                if self.peek_first() == '=' {
+                    self.advance();
+                    TokenKind::NotEqual
+                } else {
+                    TokenKind::Not
+                }
+            }
+            '&' => {
+                self.advance();
+                if self.peek_first() == '&' {
+                    self.advance();
+                    TokenKind::And
+                } else {
+                    TokenKind::BitwiseAnd
+                }
+            }
+            '|' => {
+                self.advance();
+                if self.peek_first() == '|' {
+                    self.advance();
+                    TokenKind::Or
+                } else {
+                    TokenKind::BitwiseOr
+                }
+            }
+            _ => {
+                self.advance();
+                TokenKind::Unknown
+            }
+        };
+
+        let pre_suffix_length = self.consumed();
+        let has_suffix = is_xid_start(self.peek_first())
+            && matches!(
+                kind,
+                TokenKind::Literal(LiteralKind::Int { .. })
+                    | TokenKind::Literal(LiteralKind::Float { .. })
+                    | TokenKind::Literal(LiteralKind::String { .. })
+                    | TokenKind::Literal(LiteralKind::RawString {
+                        started: true,
+                        terminated: true,
+                        ..
+                    })
+            );
+
+        if has_suffix {
+            self.eat_ident();
+        }
+
+        let length = self.consumed();
+        let suffix_start = if has_suffix { pre_suffix_length } else { length };
+        let literal = remaining[..length].to_string();
+
+        Token::new(kind, literal, location, length, suffix_start)
+    }
+}
+
+/// If `input` starts with a shebang line (`#!/usr/bin/env tree`), returns the
+/// byte length of that line (including its trailing newline, if any) so it
+/// can be skipped before lexing.
+///
+/// A leading `#![` is never treated as a shebang, reserving that sequence for
+/// inner attributes, mirroring rustc's own guard.
+pub fn strip_shebang(input: &str) -> Option<usize> {
+    let rest = input.strip_prefix("#!")?;
+
+    if rest.starts_with('[') {
+        return None;
+    }
+
+    match rest.find('\n') {
+        Some(newline) => Some(2 + newline + 1),
+        None => Some(input.len()),
     }
 }
 
+/// Lexes `input` into a stream of `Token`s, one at a time.
+///
+/// This is the main entry point for turning source text into tokens; it
+/// never panics, deferring any malformed input to the flags carried on the
+/// produced `Token`s. A leading shebang line is stripped first, with the
+/// cursor's line/column state advanced past it so token `Location`s stay correct.
+pub fn tokenize(input: &str) -> impl Iterator<Item = Token> + '_ {
+    let mut cursor = Cursor::new(input);
+
+    if let Some(shebang_len) = strip_shebang(input) {
+        while cursor.consumed() < shebang_len {
+            if cursor.advance().is_none() {
+                break;
+            }
+        }
+    }
+
+    std::iter::from_fn(move || {
+        if cursor.is_eof() {
+            None
+        } else {
+            Some(cursor.advance_token())
+        }
+    })
+}
+
 fn is_xid_start(c: char) -> bool {
     c == '_' || UnicodeXID::is_xid_start(c)
 }
@@ -370,32 +766,42 @@ mod tests {
     #[test]
     fn test_eat_number() {
         let mut binary_cursor = Cursor::new("0b10101");
-        let binary_expected = (String::from("10101"), Base::Binary);
+        let binary_expected = (String::from("10101"), Base::Binary, false);
         assert_eq!(binary_cursor.eat_number(), binary_expected);
         assert!(binary_cursor.is_eof());
 
         let mut hexadecimal_cursor = Cursor::new("0xAf123D");
-        let hexadecimal_expected = (String::from("Af123D"), Base::Hexadecimal);
+        let hexadecimal_expected = (String::from("Af123D"), Base::Hexadecimal, false);
         assert_eq!(hexadecimal_cursor.eat_number(), hexadecimal_expected);
         assert!(hexadecimal_cursor.is_eof());
 
         let mut decimal_cursor = Cursor::new("123_456");
-        let decimal_expected = (String::from("123456"), Base::Decimal);
+        let decimal_expected = (String::from("123456"), Base::Decimal, false);
         assert_eq!(decimal_cursor.eat_number(), decimal_expected);
         assert!(decimal_cursor.is_eof());
     }
 
     #[test]
-    #[should_panic]
-    fn test_eat_number_fail() {
+    fn test_eat_number_empty_base() {
+        let mut cursor = Cursor::new("0x");
+        assert_eq!(
+            cursor.eat_number(),
+            (String::new(), Base::Hexadecimal, true)
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_number_unrecognized_base() {
         let mut cursor = Cursor::new("0p123");
-        cursor.eat_number();
+        assert_eq!(cursor.eat_number(), (String::from("0"), Base::Decimal, false));
+        assert_eq!(cursor.peek_first(), 'p');
     }
 
     #[test]
     fn test_eat_comment() {
         let mut cursor = Cursor::new("// This is a comment\nballs");
-        cursor.eat_comment();
+        assert_eq!(cursor.eat_comment(), TokenKind::LineComment);
         assert_eq!(cursor.advance(), Some('\n'));
     }
 
@@ -407,7 +813,10 @@ mod tests {
                    balls";
 
         let mut cursor = Cursor::new(src);
-        cursor.eat_multiline_comment();
+        assert_eq!(
+            cursor.eat_multiline_comment(),
+            TokenKind::BlockComment { terminated: true }
+        );
         assert_eq!(cursor.advance(), Some('\n'));
     }
 
@@ -416,7 +825,10 @@ mod tests {
         let src = "/* /* nested */ */a";
 
         let mut cursor = Cursor::new(src);
-        cursor.eat_multiline_comment();
+        assert_eq!(
+            cursor.eat_multiline_comment(),
+            TokenKind::BlockComment { terminated: true }
+        );
         assert_eq!(cursor.advance(), Some('a'));
     }
 
@@ -425,7 +837,10 @@ mod tests {
         let src = "/* obama obama *l";
 
         let mut cursor = Cursor::new(src);
-        cursor.eat_multiline_comment();
+        assert_eq!(
+            cursor.eat_multiline_comment(),
+            TokenKind::BlockComment { terminated: false }
+        );
         assert_eq!(cursor.advance(), None);
     }
 
@@ -461,18 +876,466 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_eat_ident_fail() {
-        let mut cursor = Cursor::new("10Zed");
+    fn test_eat_double_quoted_string() {
+        let mut cursor = Cursor::new(r#""a b c\"""#);
 
-        cursor.eat_ident();
+        // escapes are kept raw (not decoded) here; see the `unescape` module
+        assert_eq!(
+            cursor.eat_double_quoted_string(),
+            (String::from(r#"a b c\""#), true)
+        );
+        assert!(cursor.is_eof());
     }
 
     #[test]
-    fn test_eat_double_quoted_string() {
-        let mut cursor = Cursor::new(r#""a b c\"""#);
+    fn test_eat_double_quoted_string_unterminated() {
+        let mut cursor = Cursor::new(r#""a b c"#);
+
+        assert_eq!(
+            cursor.eat_double_quoted_string(),
+            (String::from("a b c"), false)
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_double_quoted_string_unknown_escape() {
+        let mut cursor = Cursor::new(r#""a\qb""#);
+
+        assert_eq!(
+            cursor.eat_double_quoted_string(),
+            (String::from("a\\qb"), true)
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_raw_string() {
+        let mut cursor = Cursor::new(r##"r#"hi \n"# rest"##);
+
+        cursor.advance(); // eat leading 'r'
+        assert_eq!(cursor.eat_raw_string(), (1, true, true));
+        assert_eq!(cursor.peek_first(), ' ');
+    }
+
+    #[test]
+    fn test_eat_raw_string_no_hashes() {
+        let mut cursor = Cursor::new(r#"r"plain""#);
+
+        cursor.advance();
+        assert_eq!(cursor.eat_raw_string(), (0, true, true));
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_raw_string_unstarted() {
+        let mut cursor = Cursor::new("r## nope");
+
+        cursor.advance();
+        assert_eq!(cursor.eat_raw_string(), (2, false, false));
+    }
+
+    #[test]
+    fn test_eat_raw_string_unterminated() {
+        let mut cursor = Cursor::new(r##"r#"never closes"##);
+
+        cursor.advance();
+        assert_eq!(cursor.eat_raw_string(), (1, true, false));
+        assert!(cursor.is_eof());
+    }
 
-        assert_eq!(cursor.eat_double_quoted_string(), "a b c\"");
+    #[test]
+    fn test_eat_raw_string_needs_matching_hash_count() {
+        // a single closing `#` shouldn't end a literal opened with two
+        let mut cursor = Cursor::new("r##\"a\"#b\"##");
+
+        cursor.advance();
+        assert_eq!(cursor.eat_raw_string(), (2, true, true));
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_tokenize_raw_string() {
+        let tokens: Vec<Token> = tokenize(r##"r#"a "quote" in here"#"##).collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::RawString {
+                n_hashes: 1,
+                started: true,
+                terminated: true
+            })
+        );
+        assert_eq!(tokens[0].literal, r##"r#"a "quote" in here"#"##);
+    }
+
+    #[test]
+    fn test_tokenize_bare_r_is_an_ident() {
+        let tokens: Vec<Token> = tokenize("r").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Ident { keyword: false })
+        );
+        assert_eq!(tokens[0].literal, "r");
+    }
+
+    #[test]
+    fn test_tokenize_operators() {
+        let kinds: Vec<TokenKind> = tokenize("+ - <= < == != && ||")
+            .map(|token| token.kind)
+            .filter(|kind| *kind != TokenKind::Whitespace)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Add,
+                TokenKind::Minus,
+                TokenKind::LessThanEqual,
+                TokenKind::LessThan,
+                TokenKind::Equal,
+                TokenKind::NotEqual,
+                TokenKind::And,
+                TokenKind::Or,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_literals() {
+        let tokens: Vec<Token> = tokenize(r#"foo 123 0xAf "bar""#)
+            .filter(|token| token.kind != TokenKind::Whitespace)
+            .collect();
+
+        assert_eq!(tokens.len(), 4);
+
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Ident { keyword: false })
+        );
+        assert_eq!(tokens[0].literal, "foo");
+
+        assert_eq!(
+            tokens[1].kind,
+            TokenKind::Literal(LiteralKind::Int {
+                base: Base::Decimal,
+                empty_int: false
+            })
+        );
+        assert_eq!(tokens[1].literal, "123");
+
+        assert_eq!(
+            tokens[2].kind,
+            TokenKind::Literal(LiteralKind::Int {
+                base: Base::Hexadecimal,
+                empty_int: false
+            })
+        );
+        assert_eq!(tokens[2].literal, "0xAf");
+
+        assert_eq!(
+            tokens[3].kind,
+            TokenKind::Literal(LiteralKind::String { terminated: true })
+        );
+        assert_eq!(tokens[3].literal, r#""bar""#);
+    }
+
+    #[test]
+    fn test_eat_octal_digits() {
+        let mut cursor = Cursor::new("0o_17_6 ");
+
+        // move two ahead to eat '0o'
+        cursor.advance();
+        cursor.advance();
+
+        assert_eq!(cursor.eat_octal_digits(), "176");
+        assert_eq!(cursor.advance(), Some(' '));
+    }
+
+    #[test]
+    fn test_eat_numeric_literal_octal() {
+        let mut cursor = Cursor::new("0o17");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Int {
+                base: Base::Octal,
+                empty_int: false
+            }
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_numeric_literal_float() {
+        let mut cursor = Cursor::new("3.14");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Decimal,
+                empty_exponent: false,
+                invalid_base_for_float: false
+            }
+        );
         assert!(cursor.is_eof());
     }
+
+    #[test]
+    fn test_eat_numeric_literal_exponent() {
+        let mut cursor = Cursor::new("1e10");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Decimal,
+                empty_exponent: false,
+                invalid_base_for_float: false
+            }
+        );
+        assert!(cursor.is_eof());
+
+        let mut cursor = Cursor::new("1.5e-3");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Decimal,
+                empty_exponent: false,
+                invalid_base_for_float: false
+            }
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_numeric_literal_empty_exponent() {
+        let mut cursor = Cursor::new("1e");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Decimal,
+                empty_exponent: true,
+                invalid_base_for_float: false
+            }
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_numeric_literal_invalid_base_for_float() {
+        // a fractional/exponent part on a non-decimal base is still consumed
+        // as a `Float`, but flagged as invalid rather than silently accepted
+        let mut cursor = Cursor::new("0x1.5");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Hexadecimal,
+                empty_exponent: false,
+                invalid_base_for_float: true
+            }
+        );
+        assert!(cursor.is_eof());
+
+        let mut cursor = Cursor::new("0b1e2");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Float {
+                base: Base::Binary,
+                empty_exponent: false,
+                invalid_base_for_float: true
+            }
+        );
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_eat_numeric_literal_dot_without_digit_is_not_a_float() {
+        // `1.` alone is an int followed by a separate `.`, not a float,
+        // so that method calls/field access on integers keep working.
+        let mut cursor = Cursor::new("1.foo");
+        assert_eq!(
+            cursor.eat_numeric_literal(),
+            LiteralKind::Int {
+                base: Base::Decimal,
+                empty_int: false
+            }
+        );
+        assert_eq!(cursor.peek_first(), '.');
+    }
+
+    #[test]
+    fn test_tokenize_comments_are_tokens() {
+        let tokens: Vec<Token> = tokenize("a // comment\nb /* block */ c").collect();
+        let idents: Vec<&str> = tokens
+            .iter()
+            .filter(|token| matches!(token.kind, TokenKind::Literal(LiteralKind::Ident { .. })))
+            .map(|token| token.literal.as_str())
+            .collect();
+        assert_eq!(idents, vec!["a", "b", "c"]);
+
+        let comments: Vec<&Token> = tokens
+            .iter()
+            .filter(|token| {
+                matches!(
+                    token.kind,
+                    TokenKind::LineComment | TokenKind::BlockComment { .. }
+                )
+            })
+            .collect();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].kind, TokenKind::LineComment);
+        assert_eq!(comments[0].literal, "// comment");
+        assert_eq!(
+            comments[1].kind,
+            TokenKind::BlockComment { terminated: true }
+        );
+        assert_eq!(comments[1].literal, "/* block */");
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_block_comment() {
+        let tokens: Vec<Token> = tokenize("/* never closes").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::BlockComment { terminated: false }
+        );
+    }
+
+    #[test]
+    fn test_strip_shebang() {
+        assert_eq!(
+            strip_shebang("#!/usr/bin/env tree\nfoo"),
+            Some("#!/usr/bin/env tree\n".len())
+        );
+    }
+
+    #[test]
+    fn test_strip_shebang_without_trailing_newline() {
+        let src = "#!/usr/bin/env tree";
+        assert_eq!(strip_shebang(src), Some(src.len()));
+    }
+
+    #[test]
+    fn test_strip_shebang_absent() {
+        assert_eq!(strip_shebang("foo"), None);
+    }
+
+    #[test]
+    fn test_strip_shebang_reserves_inner_attribute_syntax() {
+        assert_eq!(strip_shebang("#![allow(dead_code)]"), None);
+    }
+
+    #[test]
+    fn test_tokenize_skips_shebang() {
+        let tokens: Vec<Token> = tokenize("#!/usr/bin/env tree\nfoo")
+            .filter(|token| token.kind != TokenKind::Whitespace)
+            .collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Ident { keyword: false })
+        );
+        assert_eq!(tokens[0].literal, "foo");
+        assert_eq!(tokens[0].location, Location { line: 1, column: 0 });
+    }
+
+    #[test]
+    fn test_tokenize_unknown_character() {
+        let tokens: Vec<Token> = tokenize("@").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenKind::Unknown);
+        assert_eq!(tokens[0].literal, "@");
+    }
+
+    #[test]
+    fn test_tokenize_number_suffix() {
+        let tokens: Vec<Token> = tokenize("123u8").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].literal, "123u8");
+        assert_eq!(tokens[0].length, 5);
+        assert_eq!(tokens[0].suffix_start, 3);
+    }
+
+    #[test]
+    fn test_tokenize_float_suffix() {
+        let tokens: Vec<Token> = tokenize("3.14f64").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].literal, "3.14f64");
+        assert_eq!(tokens[0].suffix_start, 4);
+    }
+
+    #[test]
+    fn test_tokenize_string_suffix() {
+        let tokens: Vec<Token> = tokenize(r#""text"s"#).collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].literal, r#""text"s"#);
+        assert_eq!(tokens[0].suffix_start, 6);
+    }
+
+    #[test]
+    fn test_tokenize_raw_string_suffix() {
+        let tokens: Vec<Token> = tokenize("r\"abc\"s").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::RawString {
+                n_hashes: 0,
+                started: true,
+                terminated: true
+            })
+        );
+        assert_eq!(tokens[0].literal, "r\"abc\"s");
+        assert_eq!(tokens[0].suffix_start, 6);
+    }
+
+    #[test]
+    fn test_tokenize_unstarted_raw_string_does_not_swallow_a_suffix() {
+        // an unstarted/malformed raw string must not eat trailing text as a
+        // "suffix" — that text needs to surface as its own token so a parser
+        // can still recover it (e.g. if it's a keyword or identifier)
+        let tokens: Vec<Token> = tokenize("r#nope").collect();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::RawString {
+                n_hashes: 1,
+                started: false,
+                terminated: false
+            })
+        );
+        assert_eq!(tokens[0].literal, "r#");
+        assert_eq!(tokens[0].suffix_start, tokens[0].length);
+
+        assert_eq!(
+            tokens[1].kind,
+            TokenKind::Literal(LiteralKind::Ident { keyword: false })
+        );
+        assert_eq!(tokens[1].literal, "nope");
+    }
+
+    #[test]
+    fn test_tokenize_no_suffix_sets_suffix_start_to_length() {
+        let tokens: Vec<Token> = tokenize("123").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].suffix_start, tokens[0].length);
+    }
+
+    #[test]
+    fn test_tokenize_ident_has_no_suffix() {
+        // suffixes only apply to number/string literals, not to identifiers
+        let tokens: Vec<Token> = tokenize("foo").collect();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].suffix_start, tokens[0].length);
+    }
 }
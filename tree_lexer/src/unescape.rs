@@ -0,0 +1,226 @@
+//! Validates the escape sequences inside an already-lexed string literal.
+//!
+//! The cursor no longer interprets escapes itself (see `eat_double_quoted_string`);
+//! it only records where the raw string contents are. This module decodes
+//! those contents on demand and reports a `Result` per character, so callers
+//! get a precise byte range for any invalid escape instead of losing it to a panic.
+
+use std::ops::Range;
+use std::str::Chars;
+
+/// An error produced while decoding an escape sequence.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub enum EscapeError {
+    /// Escape sequence is not recognized, e.g. `\q`.
+    InvalidEscape,
+    /// `\x` was not followed by two hex digits.
+    TooShortHexEscape,
+    /// `\xHH` decoded to a value greater than `0x7F`.
+    OutOfRangeHexEscape,
+    /// `\u{` was never closed with a `}`.
+    UnclosedUnicodeEscape,
+    /// `\u{}` had no hex digits inside the braces.
+    EmptyUnicodeEscape,
+    /// `\u{...}` contained a character that isn't a hex digit.
+    InvalidCharInUnicodeEscape,
+    /// `\u{...}` named a value outside the valid `char` range.
+    OutOfRangeUnicodeEscape,
+    /// `\u{...}` named a UTF-16 surrogate codepoint.
+    LoneSurrogateUnicodeEscape,
+}
+
+/// Walks the contents of an already-lexed string literal (the text between,
+/// but not including, the surrounding `"`s), decoding each escape sequence
+/// and invoking `callback` with its byte range within `literal` and the
+/// decoded `char`, or the `EscapeError` that prevented decoding it.
+///
+/// Characters that aren't part of an escape sequence are reported as `Ok` as-is.
+pub fn unescape_str(literal: &str, callback: &mut impl FnMut(Range<usize>, Result<char, EscapeError>)) {
+    let mut chars = literal.chars();
+
+    while let Some(c) = chars.next() {
+        let start = literal.len() - chars.as_str().len() - c.len_utf8();
+        let result = match c {
+            '\\' => scan_escape(&mut chars),
+            _ => Ok(c),
+        };
+        let end = literal.len() - chars.as_str().len();
+
+        callback(start..end, result);
+    }
+}
+
+/// Decodes a single escape sequence, having already consumed the leading `\`.
+fn scan_escape(chars: &mut Chars) -> Result<char, EscapeError> {
+    let c = chars.next().ok_or(EscapeError::InvalidEscape)?;
+
+    Ok(match c {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '\\' => '\\',
+        '\'' => '\'',
+        '"' => '"',
+        '0' => '\0',
+        'x' => return scan_hex_escape(chars),
+        'u' => return scan_unicode_escape(chars),
+        _ => return Err(EscapeError::InvalidEscape),
+    })
+}
+
+/// Decodes a `\xHH` ASCII hex escape, having already consumed the leading `x`.
+fn scan_hex_escape(chars: &mut Chars) -> Result<char, EscapeError> {
+    let hi = chars.next().ok_or(EscapeError::TooShortHexEscape)?;
+    let lo = chars.next().ok_or(EscapeError::TooShortHexEscape)?;
+
+    let hi = hi.to_digit(16).ok_or(EscapeError::TooShortHexEscape)?;
+    let lo = lo.to_digit(16).ok_or(EscapeError::TooShortHexEscape)?;
+    let value = hi * 16 + lo;
+
+    if value > 0x7F {
+        return Err(EscapeError::OutOfRangeHexEscape);
+    }
+
+    Ok(value as u8 as char)
+}
+
+/// Decodes a `\u{HHHHHH}` unicode escape, having already consumed the leading `u`.
+///
+/// Keeps scanning to the closing `}` (or EOF) even after the first invalid
+/// character, so the reported byte range always covers the whole escape.
+fn scan_unicode_escape(chars: &mut Chars) -> Result<char, EscapeError> {
+    if chars.next() != Some('{') {
+        return Err(EscapeError::InvalidEscape);
+    }
+
+    let mut value: u32 = 0;
+    let mut n_digits = 0u32;
+    let mut error = None;
+
+    loop {
+        match chars.next() {
+            Some('}') => break,
+            Some(c) => match c.to_digit(16) {
+                Some(digit) if n_digits < 6 => {
+                    value = value * 16 + digit;
+                    n_digits += 1;
+                }
+                Some(_) => {
+                    error.get_or_insert(EscapeError::OutOfRangeUnicodeEscape);
+                }
+                None => {
+                    error.get_or_insert(EscapeError::InvalidCharInUnicodeEscape);
+                }
+            },
+            None => return Err(error.unwrap_or(EscapeError::UnclosedUnicodeEscape)),
+        }
+    }
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    if n_digits == 0 {
+        return Err(EscapeError::EmptyUnicodeEscape);
+    }
+
+    if (0xD800..=0xDFFF).contains(&value) {
+        return Err(EscapeError::LoneSurrogateUnicodeEscape);
+    }
+
+    char::from_u32(value).ok_or(EscapeError::OutOfRangeUnicodeEscape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unescape(literal: &str) -> Vec<(Range<usize>, Result<char, EscapeError>)> {
+        let mut results = Vec::new();
+        unescape_str(literal, &mut |range, result| results.push((range, result)));
+        results
+    }
+
+    #[test]
+    fn test_unescape_plain_chars() {
+        assert_eq!(unescape("ab"), vec![(0..1, Ok('a')), (1..2, Ok('b'))]);
+    }
+
+    #[test]
+    fn test_unescape_simple_escapes() {
+        assert_eq!(unescape(r"\n"), vec![(0..2, Ok('\n'))]);
+        assert_eq!(unescape(r"\t"), vec![(0..2, Ok('\t'))]);
+        assert_eq!(unescape(r"\r"), vec![(0..2, Ok('\r'))]);
+        assert_eq!(unescape(r"\\"), vec![(0..2, Ok('\\'))]);
+        assert_eq!(unescape(r#"\""#), vec![(0..2, Ok('"'))]);
+        assert_eq!(unescape(r"\'"), vec![(0..2, Ok('\''))]);
+        assert_eq!(unescape(r"\0"), vec![(0..2, Ok('\0'))]);
+    }
+
+    #[test]
+    fn test_unescape_invalid_escape() {
+        assert_eq!(unescape(r"\q"), vec![(0..2, Err(EscapeError::InvalidEscape))]);
+    }
+
+    #[test]
+    fn test_unescape_hex_escape() {
+        assert_eq!(unescape(r"\x41"), vec![(0..4, Ok('A'))]);
+    }
+
+    #[test]
+    fn test_unescape_hex_escape_too_short() {
+        assert_eq!(unescape(r"\x4"), vec![(0..3, Err(EscapeError::TooShortHexEscape))]);
+        assert_eq!(unescape(r"\x"), vec![(0..2, Err(EscapeError::TooShortHexEscape))]);
+    }
+
+    #[test]
+    fn test_unescape_hex_escape_out_of_range() {
+        assert_eq!(unescape(r"\xFF"), vec![(0..4, Err(EscapeError::OutOfRangeHexEscape))]);
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape() {
+        assert_eq!(unescape(r"\u{48}"), vec![(0..6, Ok('H'))]);
+        assert_eq!(unescape(r"\u{1F600}"), vec![(0..9, Ok('\u{1F600}'))]);
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape_empty() {
+        assert_eq!(
+            unescape(r"\u{}"),
+            vec![(0..4, Err(EscapeError::EmptyUnicodeEscape))]
+        );
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape_unclosed() {
+        assert_eq!(
+            unescape(r"\u{48"),
+            vec![(0..5, Err(EscapeError::UnclosedUnicodeEscape))]
+        );
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape_invalid_char() {
+        assert_eq!(
+            unescape(r"\u{4z}"),
+            vec![(0..6, Err(EscapeError::InvalidCharInUnicodeEscape))]
+        );
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape_out_of_range() {
+        assert_eq!(
+            unescape(r"\u{FFFFFFF}"),
+            vec![(0..11, Err(EscapeError::OutOfRangeUnicodeEscape))]
+        );
+    }
+
+    #[test]
+    fn test_unescape_unicode_escape_lone_surrogate() {
+        assert_eq!(
+            unescape(r"\u{D800}"),
+            vec![(0..8, Err(EscapeError::LoneSurrogateUnicodeEscape))]
+        );
+    }
+}